@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Persisted, typed settings for ogp-checker. Loaded from `config.toml` in the
+/// platform config directory at startup and merged with CLI overrides.
+/// `save`/`save_to` serialize it back to TOML; nothing in the program calls
+/// them, so the file is only ever changed by hand.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub render_backend: Option<String>,
+    pub resize_filter: String,
+    pub worker_count: usize,
+    pub request_timeout_secs: u64,
+    pub user_agent: Option<String>,
+    pub theme: Theme,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            render_backend: None,
+            resize_filter: "lanczos3".to_string(),
+            worker_count: 5,
+            request_timeout_secs: 10,
+            user_agent: None,
+            theme: Theme::default(),
+        }
+    }
+}
+
+/// TUI color scheme. Values are color names (e.g. "red", "white") rather than
+/// a `ratatui::style::Color` so the config format stays a plain, human-editable
+/// TOML file; the UI layer parses them when drawing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub error_color: String,
+    pub border_color: String,
+    pub validation_warning_color: String,
+    pub validation_error_color: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            error_color: "red".to_string(),
+            border_color: "white".to_string(),
+            validation_warning_color: "yellow".to_string(),
+            validation_error_color: "red".to_string(),
+        }
+    }
+}
+
+/// Describes one `Config` setting for introspection (e.g. the
+/// `--list-config` flag or generated docs): its name, type, default value,
+/// and a one-line description.
+pub struct ConfigFieldInfo {
+    pub name: &'static str,
+    pub type_name: &'static str,
+    pub default: String,
+    pub description: &'static str,
+}
+
+pub fn field_registry() -> Vec<ConfigFieldInfo> {
+    let defaults = Config::default();
+    vec![
+        ConfigFieldInfo {
+            name: "render_backend",
+            type_name: "Option<String>",
+            default: defaults
+                .render_backend
+                .clone()
+                .unwrap_or_else(|| "auto-detect".to_string()),
+            description: "Terminal image backend: kitty, sixel, or canvas.",
+        },
+        ConfigFieldInfo {
+            name: "resize_filter",
+            type_name: "String",
+            default: defaults.resize_filter.clone(),
+            description: "Resampling filter used when downscaling images.",
+        },
+        ConfigFieldInfo {
+            name: "worker_count",
+            type_name: "usize",
+            default: defaults.worker_count.to_string(),
+            description: "Number of concurrent fetch workers in batch mode.",
+        },
+        ConfigFieldInfo {
+            name: "request_timeout_secs",
+            type_name: "u64",
+            default: defaults.request_timeout_secs.to_string(),
+            description: "Per-request timeout, in seconds.",
+        },
+        ConfigFieldInfo {
+            name: "user_agent",
+            type_name: "Option<String>",
+            default: defaults
+                .user_agent
+                .clone()
+                .unwrap_or_else(|| "ogp-checker/<version>".to_string()),
+            description: "Default User-Agent sent with every request.",
+        },
+        ConfigFieldInfo {
+            name: "theme.*",
+            type_name: "String (color name)",
+            default: format!(
+                "error={}, border={}, validation_warning={}, validation_error={}",
+                defaults.theme.error_color,
+                defaults.theme.border_color,
+                defaults.theme.validation_warning_color,
+                defaults.theme.validation_error_color
+            ),
+            description: "TUI colors for errors, borders, and validation findings.",
+        },
+    ]
+}
+
+/// `$XDG_CONFIG_HOME/ogp-checker/config.toml` (or the platform equivalent).
+pub fn default_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("ogp-checker")
+        .join("config.toml")
+}
+
+pub fn load() -> Config {
+    load_from(&default_path())
+}
+
+pub fn load_from(path: &Path) -> Config {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| toml::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(config: &Config) -> std::io::Result<()> {
+    save_to(config, &default_path())
+}
+
+pub fn save_to(config: &Config, path: &Path) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let contents = toml::to_string_pretty(config).unwrap_or_default();
+    std::fs::write(path, contents)
+}
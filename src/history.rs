@@ -0,0 +1,43 @@
+use std::path::{Path, PathBuf};
+
+/// How many recently-checked URLs are kept. Old entries fall off the back
+/// once a session pushes past this so the file doesn't grow unbounded.
+const MAX_ENTRIES: usize = 50;
+
+/// `$XDG_CONFIG_HOME/ogp-checker/history.txt` (or the platform equivalent).
+pub fn default_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("ogp-checker")
+        .join("history.txt")
+}
+
+pub fn load() -> Vec<String> {
+    load_from(&default_path())
+}
+
+pub fn load_from(path: &Path) -> Vec<String> {
+    std::fs::read_to_string(path)
+        .map(|contents| contents.lines().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Move `url` to the front of `history`, deduplicating and capping at
+/// `MAX_ENTRIES`, then persist the result so it survives to the next run.
+pub fn record(history: &mut Vec<String>, url: &str) {
+    record_to(history, url, &default_path());
+}
+
+pub fn record_to(history: &mut Vec<String>, url: &str, path: &Path) {
+    history.retain(|existing| existing != url);
+    history.insert(0, url.to_string());
+    history.truncate(MAX_ENTRIES);
+    let _ = save_to(history, path);
+}
+
+fn save_to(history: &[String], path: &Path) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, history.join("\n"))
+}
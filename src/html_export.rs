@@ -0,0 +1,128 @@
+use crate::ogp::OGPInfo;
+use std::io;
+use std::path::Path;
+
+/// Which Twitter Card layout to render: `summary` shows a small thumbnail
+/// beside the text; `summary_large_image` shows the image full-width above it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CardTemplate {
+    Summary,
+    SummaryLargeImage,
+}
+
+impl CardTemplate {
+    fn detect(info: &OGPInfo) -> Self {
+        match info.tags.get("twitter:card").map(String::as_str) {
+            Some("summary_large_image") => CardTemplate::SummaryLargeImage,
+            _ => CardTemplate::Summary,
+        }
+    }
+}
+
+/// Render `info` as a self-contained HTML page previewing how it would
+/// appear as a Twitter/Facebook/Discord-style share card.
+pub fn render(info: &OGPInfo) -> String {
+    let template = CardTemplate::detect(info);
+    let site_name = escape(info.tags.get("og:site_name").map(String::as_str).unwrap_or_default());
+    let title = escape(&info.title);
+    let description = escape(&info.description);
+    let image = escape(&info.image);
+
+    let image_class = match template {
+        CardTemplate::SummaryLargeImage => "card-image large",
+        CardTemplate::Summary => "card-image summary",
+    };
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title} - OGP preview</title>
+<style>
+  body {{ font-family: sans-serif; background: #15202b; color: #e7e9ea; display: flex; justify-content: center; padding: 2rem; }}
+  .card {{ border: 1px solid #38444d; border-radius: 12px; overflow: hidden; max-width: 500px; background: #192734; }}
+  .card-image.large {{ width: 100%; display: block; }}
+  .card-image.summary {{ width: 120px; height: 120px; object-fit: cover; float: left; }}
+  .card-text {{ padding: 0.75rem 1rem; overflow: hidden; }}
+  .card-site {{ color: #8899a6; font-size: 0.85rem; }}
+  .card-title {{ font-weight: bold; margin: 0.2rem 0; }}
+  .card-description {{ color: #8899a6; font-size: 0.9rem; }}
+</style>
+</head>
+<body>
+<div class="card">
+  <img class="{image_class}" src="{image}" alt="">
+  <div class="card-text">
+    <div class="card-site">{site_name}</div>
+    <div class="card-title">{title}</div>
+    <div class="card-description">{description}</div>
+  </div>
+</div>
+</body>
+</html>
+"#
+    )
+}
+
+pub fn write_to(info: &OGPInfo, path: &Path) -> io::Result<()> {
+    std::fs::write(path, render(info))
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn info(card: Option<&str>) -> OGPInfo {
+        let mut tags = BTreeMap::new();
+        tags.insert("og:title".to_string(), "<Title> & \"Quoted\"".to_string());
+        if let Some(card) = card {
+            tags.insert("twitter:card".to_string(), card.to_string());
+        }
+        OGPInfo {
+            title: "<Title> & \"Quoted\"".to_string(),
+            description: "A <script>alert(1)</script> description".to_string(),
+            image: "https://example.com/a.png?x=1&y=2".to_string(),
+            tags,
+        }
+    }
+
+    #[test]
+    fn escapes_all_four_special_characters() {
+        assert_eq!(
+            escape(r#"<a href="x">A & B</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;A &amp; B&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn rendered_page_never_contains_raw_markup_from_fields() {
+        let html = render(&info(None));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;Title&gt;"));
+        assert!(html.contains("&amp;"));
+    }
+
+    #[test]
+    fn defaults_to_summary_layout_without_a_twitter_card_tag() {
+        assert_eq!(CardTemplate::detect(&info(None)), CardTemplate::Summary);
+        assert!(render(&info(None)).contains("card-image summary"));
+    }
+
+    #[test]
+    fn summary_large_image_card_picks_the_large_layout() {
+        assert_eq!(
+            CardTemplate::detect(&info(Some("summary_large_image"))),
+            CardTemplate::SummaryLargeImage
+        );
+        assert!(render(&info(Some("summary_large_image"))).contains("card-image large"));
+    }
+}
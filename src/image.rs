@@ -1,4 +1,6 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
 use image::{DynamicImage, GenericImageView};
+use std::env;
 
 pub struct Image {
     pub width: u32,
@@ -6,18 +8,317 @@ pub struct Image {
     pub pixels: Vec<(u8, u8, u8)>,
 }
 
+/// Terminal cells are roughly twice as tall as they are wide, so an image
+/// sampled 1:1 against a cell grid looks squashed vertically unless corrected.
+const CELL_HEIGHT_TO_WIDTH_RATIO: f64 = 2.0;
+
+/// Default size (in terminal cells) an image is downscaled to before it is
+/// cached, chosen to comfortably cover the image pane at common terminal sizes.
+const DEFAULT_TARGET_COLUMNS: u32 = 120;
+const DEFAULT_TARGET_ROWS: u32 = 60;
+
 impl Image {
     pub fn from_dynamic_image(img: &DynamicImage) -> Self {
-        let (width, height) = img.dimensions();
-        let pixels = img
+        Self::from_dynamic_image_with_filter(img, ResizeFilter::default())
+    }
+
+    /// Downscale `img` to fit within the default terminal cell grid using
+    /// `filter`, preserving the source aspect ratio and correcting for the
+    /// ~2:1 height-to-width ratio of a terminal cell, before sampling pixels.
+    pub fn from_dynamic_image_with_filter(img: &DynamicImage, filter: ResizeFilter) -> Self {
+        let (src_width, src_height) = img.dimensions();
+
+        let available_rows_in_pixels = DEFAULT_TARGET_ROWS as f64 * CELL_HEIGHT_TO_WIDTH_RATIO;
+        let scale = f64::min(
+            DEFAULT_TARGET_COLUMNS as f64 / src_width as f64,
+            available_rows_in_pixels / src_height as f64,
+        )
+        .min(1.0);
+
+        let target_width = ((src_width as f64 * scale).round() as u32).max(1);
+        let target_height =
+            ((src_height as f64 * scale / CELL_HEIGHT_TO_WIDTH_RATIO).round() as u32).max(1);
+
+        let resized = image::imageops::resize(
+            img,
+            target_width,
+            target_height,
+            filter.to_filter_type(),
+        );
+
+        let pixels = resized
             .pixels()
             .map(|(_, _, p)| (p[0], p[1], p[2]))
             .collect();
 
         Image {
-            width,
-            height,
+            width: target_width,
+            height: target_height,
             pixels,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blank_image(width: u32, height: u32) -> DynamicImage {
+        DynamicImage::new_rgb8(width, height)
+    }
+
+    #[test]
+    fn never_upscales_a_small_image() {
+        let img = Image::from_dynamic_image(&blank_image(50, 50));
+        assert_eq!((img.width, img.height), (50, 25));
+    }
+
+    #[test]
+    fn downscales_a_large_square_image_to_fill_the_grid() {
+        let img = Image::from_dynamic_image(&blank_image(1200, 1200));
+        assert_eq!((img.width, img.height), (120, 60));
+    }
+
+    #[test]
+    fn a_wide_image_is_limited_by_the_column_budget() {
+        let img = Image::from_dynamic_image(&blank_image(2400, 600));
+        assert_eq!((img.width, img.height), (120, 15));
+    }
+
+    #[test]
+    fn a_tall_image_is_limited_by_the_row_budget() {
+        let img = Image::from_dynamic_image(&blank_image(600, 2400));
+        assert_eq!((img.width, img.height), (30, 60));
+    }
+
+    #[test]
+    fn target_dimensions_are_never_rounded_down_to_zero() {
+        let img = Image::from_dynamic_image(&blank_image(1, 1));
+        assert_eq!((img.width, img.height), (1, 1));
+    }
+
+    #[test]
+    fn pixel_count_matches_target_dimensions() {
+        let img = Image::from_dynamic_image(&blank_image(1200, 1200));
+        assert_eq!(img.pixels.len(), (img.width * img.height) as usize);
+    }
+}
+
+/// Resampling filter used when downscaling a fetched image to the terminal
+/// cell grid. Slower filters (towards `Lanczos3`) trade speed for sharper,
+/// less aliased results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResizeFilter {
+    Nearest,
+    Triangle,
+    CatmullRom,
+    Lanczos3,
+}
+
+impl Default for ResizeFilter {
+    fn default() -> Self {
+        ResizeFilter::Lanczos3
+    }
+}
+
+impl ResizeFilter {
+    pub fn to_filter_type(self) -> image::imageops::FilterType {
+        match self {
+            ResizeFilter::Nearest => image::imageops::FilterType::Nearest,
+            ResizeFilter::Triangle => image::imageops::FilterType::Triangle,
+            ResizeFilter::CatmullRom => image::imageops::FilterType::CatmullRom,
+            ResizeFilter::Lanczos3 => image::imageops::FilterType::Lanczos3,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "nearest" => Some(ResizeFilter::Nearest),
+            "triangle" => Some(ResizeFilter::Triangle),
+            "catmullrom" | "catmull-rom" => Some(ResizeFilter::CatmullRom),
+            "lanczos3" | "lanczos" => Some(ResizeFilter::Lanczos3),
+            _ => None,
+        }
+    }
+}
+
+/// Terminal graphics protocol used to render a cached `Image`.
+///
+/// `Canvas` is the lowest-common-denominator fallback (one ratatui cell per
+/// sampled pixel); `Kitty` and `Sixel` push full-resolution pixel data to
+/// terminals that understand the respective escape sequences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderBackend {
+    Kitty,
+    Sixel,
+    Canvas,
+}
+
+impl RenderBackend {
+    /// Best-effort detection of the richest protocol the host terminal supports,
+    /// based on the environment variables terminals conventionally set.
+    pub fn detect() -> Self {
+        if env::var("KITTY_WINDOW_ID").is_ok() {
+            return RenderBackend::Kitty;
+        }
+
+        let term_program = env::var("TERM_PROGRAM").unwrap_or_default();
+        if term_program.eq_ignore_ascii_case("konsole")
+            || env::var("WEZTERM_EXECUTABLE").is_ok()
+        {
+            return RenderBackend::Kitty;
+        }
+
+        let term = env::var("TERM").unwrap_or_default();
+        if term.contains("kitty") {
+            return RenderBackend::Kitty;
+        }
+        if term.contains("mlterm") || term.contains("sixel") || term_program.eq_ignore_ascii_case("iterm.app") {
+            return RenderBackend::Sixel;
+        }
+
+        RenderBackend::Canvas
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "kitty" => Some(RenderBackend::Kitty),
+            "sixel" => Some(RenderBackend::Sixel),
+            "canvas" => Some(RenderBackend::Canvas),
+            _ => None,
+        }
+    }
+}
+
+const KITTY_CHUNK_SIZE: usize = 4096;
+
+/// Encode `img` as one or more Kitty graphics protocol escape sequences, each
+/// carrying at most `KITTY_CHUNK_SIZE` bytes of base64 payload. Every chunk but
+/// the last sets `m=1` to signal more data is coming.
+pub fn encode_kitty(img: &Image) -> Vec<String> {
+    let mut raw = Vec::with_capacity(img.pixels.len() * 3);
+    for (r, g, b) in &img.pixels {
+        raw.push(*r);
+        raw.push(*g);
+        raw.push(*b);
+    }
+
+    let encoded = STANDARD.encode(&raw);
+    let chunks: Vec<&[u8]> = encoded.as_bytes().chunks(KITTY_CHUNK_SIZE).collect();
+    let chunk_count = chunks.len().max(1);
+
+    chunks
+        .into_iter()
+        .enumerate()
+        .map(|(i, chunk)| {
+            let more = if i + 1 < chunk_count { 1 } else { 0 };
+            let payload = std::str::from_utf8(chunk).unwrap_or_default();
+            if i == 0 {
+                format!(
+                    "\x1b_Ga=T,f=24,s={},v={},m={};{}\x1b\\",
+                    img.width, img.height, more, payload
+                )
+            } else {
+                format!("\x1b_Gm={};{}\x1b\\", more, payload)
+            }
+        })
+        .collect()
+}
+
+/// Quantize `img` to a 6x6x6 color cube and emit a Sixel (`ESC P q ... ESC \`)
+/// stream. This keeps the encoder simple while still producing a real,
+/// capable-terminal-renderable sixel image rather than a blocky approximation.
+pub fn encode_sixel(img: &Image) -> String {
+    const LEVELS: u8 = 6;
+    let quantize = |c: u8| -> u8 {
+        ((c as u16 * (LEVELS as u16 - 1) + 127) / 255) as u8
+    };
+
+    let palette_index = |r: u8, g: u8, b: u8| -> usize {
+        let (r, g, b) = (quantize(r) as usize, quantize(g) as usize, quantize(b) as usize);
+        r * (LEVELS as usize) * (LEVELS as usize) + g * (LEVELS as usize) + b
+    };
+    let level_to_pct = |l: u8| -> u32 { (l as u32 * 100) / (LEVELS as u32 - 1) };
+
+    let width = img.width as usize;
+    let height = img.height as usize;
+
+    let mut out = String::from("\x1bPq");
+
+    for p in 0..(LEVELS as usize).pow(3) {
+        let r = (p / (LEVELS as usize * LEVELS as usize)) as u8;
+        let g = ((p / LEVELS as usize) % LEVELS as usize) as u8;
+        let b = (p % LEVELS as usize) as u8;
+        out.push_str(&format!(
+            "#{};2;{};{};{}",
+            p,
+            level_to_pct(r),
+            level_to_pct(g),
+            level_to_pct(b)
+        ));
+    }
+
+    for band_start in (0..height).step_by(6) {
+        let band_height = (height - band_start).min(6);
+        let mut used_colors = Vec::new();
+        for x in 0..width {
+            for row in 0..band_height {
+                let (r, g, b) = img.pixels[(band_start + row) * width + x];
+                let idx = palette_index(r, g, b);
+                if !used_colors.contains(&idx) {
+                    used_colors.push(idx);
+                }
+            }
+        }
+
+        for (ci, &color) in used_colors.iter().enumerate() {
+            out.push('#');
+            out.push_str(&color.to_string());
+
+            let mut run_char = 0u8;
+            let mut run_len = 0usize;
+            for x in 0..width {
+                let mut sixel_bits = 0u8;
+                for row in 0..band_height {
+                    let (r, g, b) = img.pixels[(band_start + row) * width + x];
+                    if palette_index(r, g, b) == color {
+                        sixel_bits |= 1 << row;
+                    }
+                }
+                let c = sixel_bits + 0x3f;
+                if run_len > 0 && c == run_char {
+                    run_len += 1;
+                } else {
+                    if run_len > 0 {
+                        push_sixel_run(&mut out, run_char, run_len);
+                    }
+                    run_char = c;
+                    run_len = 1;
+                }
+            }
+            if run_len > 0 {
+                push_sixel_run(&mut out, run_char, run_len);
+            }
+
+            if ci + 1 < used_colors.len() {
+                out.push('$');
+            }
+        }
+        out.push('-');
+    }
+
+    out.push_str("\x1b\\");
+    out
+}
+
+fn push_sixel_run(out: &mut String, c: u8, len: usize) {
+    if len > 3 {
+        out.push('!');
+        out.push_str(&len.to_string());
+        out.push(c as char);
+    } else {
+        for _ in 0..len {
+            out.push(c as char);
+        }
+    }
+}
@@ -1,9 +1,16 @@
+mod config;
+mod history;
+mod html_export;
 mod image;
 mod ogp;
 mod ui;
+mod validate;
 
 use clap::Parser;
-use ogp::{fetch_ogp_info, normalize_url, AppState, OGPInfo};
+use image::{RenderBackend, ResizeFilter};
+use ogp::{fetch_ogp_info, normalize_url, AppState, OGPInfo, RequestContext};
+use std::io::{self, BufRead};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use ui::UI;
@@ -11,45 +18,258 @@ use ui::UI;
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    #[arg(default_value = "")]
-    url: String,
+    /// URL(s) to check. Omit entirely to launch the interactive TUI.
+    urls: Vec<String>,
 
     #[arg(short, long)]
     json: bool,
+
+    /// Read additional URLs (one per line) from a file, or "-" for stdin.
+    #[arg(long)]
+    input: Option<String>,
+
+    /// Number of concurrent fetch workers used in batch mode. Defaults to the
+    /// configured `worker_count` (see `config.toml`).
+    #[arg(long)]
+    workers: Option<usize>,
+
+    /// Force a terminal image backend instead of auto-detecting one (kitty, sixel, canvas).
+    #[arg(long)]
+    render_backend: Option<String>,
+
+    /// Resampling filter used when downscaling images (nearest, triangle, catmullrom, lanczos3).
+    #[arg(long)]
+    resize_filter: Option<String>,
+
+    /// Extra request header as "Name: Value". May be given multiple times.
+    #[arg(long = "header")]
+    headers: Vec<String>,
+
+    /// Raw Cookie header value to send with every request.
+    #[arg(long)]
+    cookie: Option<String>,
+
+    /// Override the default User-Agent sent with every request.
+    #[arg(long = "user-agent")]
+    user_agent: Option<String>,
+
+    /// Bearer token sent as "Authorization: Bearer <token>" with every request.
+    #[arg(long)]
+    token: Option<String>,
+
+    /// Path to a config.toml to load instead of the platform default.
+    #[arg(long)]
+    config: Option<String>,
+
+    /// Print every configurable setting, its type, and its default, then exit.
+    #[arg(long)]
+    list_config: bool,
+
+    /// Write a static HTML social-card preview of the first successfully
+    /// fetched page (summary or summary_large_image, chosen from twitter:card).
+    #[arg(long)]
+    html: Option<String>,
+}
+
+fn build_request_context(args: &Args, config: &config::Config) -> RequestContext {
+    let headers = args
+        .headers
+        .iter()
+        .filter_map(|raw| raw.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect();
+
+    let user_agent = args
+        .user_agent
+        .clone()
+        .or_else(|| config.user_agent.clone())
+        .unwrap_or_else(|| format!("ogp-checker/{}", env!("CARGO_PKG_VERSION")));
+
+    RequestContext::with_settings(
+        user_agent,
+        headers,
+        args.cookie.clone(),
+        args.token.clone(),
+        10,
+        config.request_timeout_secs,
+    )
 }
 
 #[tokio::main]
 async fn main() {
     let args = Args::parse();
 
-    if !args.url.is_empty() {
-        let client = reqwest::Client::new();
-        match fetch_ogp_info(&client, &normalize_url(&args.url)).await {
-            Ok(ogp_info) => {
-                if args.json {
-                    match serde_json::to_string_pretty(&ogp_info) {
-                        Ok(json) => println!("{}", json),
-                        Err(e) => eprintln!("Error serializing OGP info: {}", e),
+    if args.list_config {
+        print_config_fields();
+        return;
+    }
+
+    let config_path = args
+        .config
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(config::default_path);
+    let config = config::load_from(&config_path);
+
+    let mut urls = args.urls.clone();
+    if let Some(path) = &args.input {
+        match read_urls_from_input(path) {
+            Ok(mut extra) => urls.append(&mut extra),
+            Err(e) => eprintln!("Error reading URLs from {}: {}", path, e),
+        }
+    }
+
+    let request_context = build_request_context(&args, &config);
+
+    if !urls.is_empty() {
+        let worker_count = args.workers.unwrap_or(config.worker_count);
+        let results = run_batch(urls, worker_count, request_context).await;
+
+        if let Some(path) = &args.html {
+            match results.iter().find_map(|(_, result)| result.as_ref().ok()) {
+                Some(info) => match html_export::write_to(info, Path::new(path)) {
+                    Ok(()) => println!("Wrote HTML preview to {}", path),
+                    Err(e) => eprintln!("Error writing HTML preview to {}: {}", path, e),
+                },
+                None => eprintln!("No OGP info fetched successfully; skipping HTML preview"),
+            }
+        }
+
+        if args.json {
+            let json_results: Vec<_> = results
+                .iter()
+                .map(|(url, result)| match result {
+                    Ok(info) => {
+                        serde_json::json!({ "url": url, "ogp": info, "validation": validate::validate(info) })
                     }
-                } else {
-                    print_ogp_info(&ogp_info);
+                    Err(err) => serde_json::json!({ "url": url, "error": err }),
+                })
+                .collect();
+            match serde_json::to_string_pretty(&json_results) {
+                Ok(json) => println!("{}", json),
+                Err(e) => eprintln!("Error serializing OGP info: {}", e),
+            }
+        } else {
+            for (url, result) in &results {
+                println!("== {} ==", url);
+                match result {
+                    Ok(info) => print_ogp_info(info),
+                    Err(err) => eprintln!("Error fetching OGP info: {}", err),
                 }
             }
-            Err(e) => eprintln!("Error fetching OGP info: {}", e),
         }
     } else {
-        let state = Arc::new(Mutex::new(AppState::new()));
-        let ui = UI::new(state);
+        let mut app_state = AppState::from_config(&config);
+        if let Some(name) = &args.render_backend {
+            match RenderBackend::from_name(name) {
+                Some(backend) => app_state.render_backend = backend,
+                None => eprintln!("Unknown render backend \"{}\", keeping the configured or auto-detected one", name),
+            }
+        }
+        if let Some(name) = &args.resize_filter {
+            match ResizeFilter::from_name(name) {
+                Some(filter) => app_state.resize_filter = filter,
+                None => eprintln!("Unknown resize filter \"{}\", keeping the configured or default one", name),
+            }
+        }
+
+        app_state.history = history::load();
+
+        let state = Arc::new(Mutex::new(app_state));
+        let ui = UI::with_config_path(state, request_context, config_path);
         ui.run().await.unwrap();
     }
 }
 
+/// Print every configurable setting, its type, and its default for `--list-config`.
+fn print_config_fields() {
+    for field in config::field_registry() {
+        println!("{} ({}): {}", field.name, field.type_name, field.default);
+        println!("    {}", field.description);
+    }
+}
+
 fn print_ogp_info(ogp_info: &OGPInfo) {
     println!("Title: {}", ogp_info.title);
     println!("Description: {}", ogp_info.description);
     println!("Image URL: {}", ogp_info.image);
-    println!("Metadata:");
-    for (tag, content) in &ogp_info.metadata {
+    println!("Tags:");
+    for (tag, content) in &ogp_info.tags {
         println!("\"{}\" - \"{}\"", tag, content);
     }
+
+    let issues = validate::validate(ogp_info);
+    if !issues.is_empty() {
+        println!("Validation:");
+        for issue in &issues {
+            println!("[{:?}] {}", issue.severity, issue.message);
+        }
+    }
+}
+
+fn read_urls_from_input(path: &str) -> io::Result<Vec<String>> {
+    let lines: Vec<String> = if path == "-" {
+        io::stdin().lock().lines().collect::<io::Result<_>>()?
+    } else {
+        let file = std::fs::File::open(path)?;
+        io::BufReader::new(file).lines().collect::<io::Result<_>>()?
+    };
+
+    Ok(lines
+        .into_iter()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
+/// Fetch OGP info for every URL in `urls` through a fixed-size pool of
+/// `worker_count` workers pulling jobs off a shared channel, preserving the
+/// input order in the returned results.
+async fn run_batch(
+    urls: Vec<String>,
+    worker_count: usize,
+    ctx: RequestContext,
+) -> Vec<(String, Result<OGPInfo, String>)> {
+    let worker_count = worker_count.max(1);
+
+    let (job_tx, job_rx) = tokio::sync::mpsc::channel::<(usize, String)>(urls.len().max(1));
+    for (index, url) in urls.iter().enumerate() {
+        job_tx.send((index, url.clone())).await.unwrap();
+    }
+    drop(job_tx);
+
+    let job_rx = Arc::new(Mutex::new(job_rx));
+    let results = Arc::new(Mutex::new(vec![None; urls.len()]));
+
+    let mut workers = Vec::with_capacity(worker_count);
+    for _ in 0..worker_count {
+        let job_rx = Arc::clone(&job_rx);
+        let results = Arc::clone(&results);
+        let ctx = ctx.clone();
+
+        workers.push(tokio::spawn(async move {
+            loop {
+                let job = job_rx.lock().await.recv().await;
+                let Some((index, url)) = job else {
+                    break;
+                };
+
+                let outcome = fetch_ogp_info(&ctx, &normalize_url(&url))
+                    .await
+                    .map_err(|e| e.to_string());
+                results.lock().await[index] = Some((url, outcome));
+            }
+        }));
+    }
+
+    for worker in workers {
+        let _ = worker.await;
+    }
+
+    Arc::try_unwrap(results)
+        .unwrap_or_else(|arc| panic!("worker still holds a reference to results: {:?}", Arc::strong_count(&arc)))
+        .into_inner()
+        .into_iter()
+        .map(|slot| slot.expect("every job index is filled exactly once"))
+        .collect()
 }
@@ -1,68 +1,312 @@
-use crate::image::Image;
-use reqwest::{self, Client};
+use crate::image::{Image, RenderBackend, ResizeFilter};
+use reqwest::{
+    self,
+    header::{HeaderMap, HeaderName, HeaderValue, COOKIE},
+    Client, Method,
+};
 use scraper::{Html, Selector};
 use image::DynamicImage;
-use crossterm::{
-    event::{self, Event, KeyCode},
-    execute,
-    terminal::{enable_raw_mode, disable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
-use ratatui::{
-    backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout},
-    style::{Style, Color},
-    widgets::{Block, Borders, Paragraph, canvas::{Canvas, Rectangle}},
-    Terminal,
-};
-use std::{sync::Arc, io};
-use tokio::sync::{Mutex, watch};
-
-#[derive(Clone)]
+use crate::config::{Config, Theme};
+use crate::history;
+use crate::validate::{self, ValidationIssue};
+use std::collections::BTreeMap;
+use std::io;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// A parsed page's Open Graph / Twitter Card data. `tags` holds every
+/// recognized `og:*`, `twitter:*`, `article:*`, and `product:*` meta tag
+/// keyed by its `property`/`name` attribute; `title`/`description`/`image`
+/// are pulled out separately since nearly every consumer of `OGPInfo` wants them.
+#[derive(Clone, serde::Serialize)]
 pub struct OGPInfo {
     pub title: String,
     pub description: String,
     pub image: String,
-    pub metadata: Vec<String>,
+    pub tags: BTreeMap<String, String>,
 }
 
-pub struct AppState {
+/// One inspected page: its own URL input, fetched OGP data, cached image, and
+/// scroll/error state. `AppState` keeps a `Vec<Tab>` so a second site can be
+/// opened for comparison without losing the first. `id` is stable for the
+/// tab's lifetime (unlike its position in `tabs`, which shifts as tabs open,
+/// close, or reorder) so an in-flight fetch can find its way back to the
+/// right tab even if the user has since switched away from or closed others.
+pub struct Tab {
+    pub id: u64,
     pub url: String,
     pub cursor_position: usize,
     pub ogp_info: Option<OGPInfo>,
     pub cached_image: Option<Image>,
+    /// Bumped every time `cached_image` is reassigned, so the redraw loop can
+    /// tell "still the same picture" from "needs retransmitting" without
+    /// comparing pixel data, and knows when a previously-sent terminal-graphics
+    /// placement is now stale and must be deleted.
+    pub image_version: u64,
     pub error_message: Option<String>,
+    /// A transient, non-error notice (e.g. "wrote HTML preview to ..."),
+    /// cleared whenever a new fetch completes or the tab's URL is cleared.
+    pub status_message: Option<String>,
+    pub metadata_offset: usize,
+    pub validation: Vec<ValidationIssue>,
 }
 
-impl AppState {
-    pub fn new() -> Self {
+impl Tab {
+    pub fn new(id: u64) -> Self {
         Self {
+            id,
             url: String::new(),
             cursor_position: 0,
             ogp_info: None,
             cached_image: None,
+            image_version: 0,
             error_message: None,
+            status_message: None,
+            metadata_offset: 0,
+            validation: Vec::new(),
         }
     }
 
     pub fn normalize_url(&self) -> String {
-        if self.url.starts_with("http://") || self.url.starts_with("https://") {
-            self.url.clone()
+        normalize_url(&self.url)
+    }
+
+    /// A short label for the tab bar: the current URL, or "new tab" while empty.
+    pub fn label(&self) -> &str {
+        if self.url.is_empty() {
+            "new tab"
         } else {
-            format!("http://{}", self.url)
+            &self.url
+        }
+    }
+}
+
+pub struct AppState {
+    pub tabs: Vec<Tab>,
+    pub active_tab: usize,
+    pub next_tab_id: u64,
+    pub render_backend: RenderBackend,
+    pub resize_filter: ResizeFilter,
+    pub theme: Theme,
+    pub history: Vec<String>,
+    pub history_visible: bool,
+    pub history_selected: usize,
+}
+
+impl AppState {
+    pub fn new() -> Self {
+        Self {
+            tabs: vec![Tab::new(0)],
+            active_tab: 0,
+            next_tab_id: 1,
+            render_backend: RenderBackend::detect(),
+            resize_filter: ResizeFilter::default(),
+            theme: Theme::default(),
+            history: Vec::new(),
+            history_visible: false,
+            history_selected: 0,
+        }
+    }
+
+    /// Build an `AppState` with config-provided defaults (render backend,
+    /// resize filter, theme) layered over the usual auto-detected ones.
+    pub fn from_config(config: &Config) -> Self {
+        let mut state = Self::new();
+
+        if let Some(name) = &config.render_backend {
+            if let Some(backend) = RenderBackend::from_name(name) {
+                state.render_backend = backend;
+            }
+        }
+        if let Some(filter) = ResizeFilter::from_name(&config.resize_filter) {
+            state.resize_filter = filter;
+        }
+        state.theme = config.theme.clone();
+
+        state
+    }
+
+    pub fn active_tab(&self) -> &Tab {
+        &self.tabs[self.active_tab]
+    }
+
+    pub fn active_tab_mut(&mut self) -> &mut Tab {
+        &mut self.tabs[self.active_tab]
+    }
+
+    pub fn tab_by_id_mut(&mut self, id: u64) -> Option<&mut Tab> {
+        self.tabs.iter_mut().find(|tab| tab.id == id)
+    }
+
+    /// Open a new empty tab and switch to it.
+    pub fn open_tab(&mut self) {
+        let id = self.next_tab_id;
+        self.next_tab_id += 1;
+        self.tabs.push(Tab::new(id));
+        self.active_tab = self.tabs.len() - 1;
+    }
+
+    /// Close the active tab, unless it's the only one left. `Vec::remove`
+    /// shifts everything after the closed tab down one slot, so the tab that
+    /// was to its *right* becomes active at the same index (or the new last
+    /// tab, if the closed tab was rightmost).
+    pub fn close_active_tab(&mut self) {
+        if self.tabs.len() <= 1 {
+            return;
+        }
+        self.tabs.remove(self.active_tab);
+        if self.active_tab >= self.tabs.len() {
+            self.active_tab = self.tabs.len() - 1;
+        }
+    }
+
+    pub fn next_tab(&mut self) {
+        self.active_tab = (self.active_tab + 1) % self.tabs.len();
+    }
+
+    pub fn prev_tab(&mut self) {
+        self.active_tab = (self.active_tab + self.tabs.len() - 1) % self.tabs.len();
+    }
+
+    /// Open a new tab pre-filled with the URL at `index` in `history` and
+    /// make it active, so a previously-checked site can be reopened. Does
+    /// nothing if `index` is out of range.
+    pub fn open_history_entry(&mut self, index: usize) {
+        let Some(url) = self.history.get(index).cloned() else {
+            return;
+        };
+        self.open_tab();
+        let tab = self.active_tab_mut();
+        tab.url = url;
+        tab.cursor_position = tab.url.len();
+        self.history_visible = false;
+    }
+}
+
+/// Prefix a bare host/path with `http://` if it doesn't already carry a scheme.
+pub fn normalize_url(url: &str) -> String {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        url.to_string()
+    } else {
+        format!("http://{}", url)
+    }
+}
+
+/// How requests to the target page and its `og:image` are made: the
+/// `User-Agent`, any extra headers / cookie, an optional bearer token, and a
+/// redirect limit. Built once and threaded through `update_ogp`,
+/// `fetch_ogp_info`, and `fetch_dynamic_image` so every request in a run is
+/// made the same way, which matters for sites that gate OGP tags behind auth.
+#[derive(Clone)]
+pub struct RequestContext {
+    pub client: Client,
+    pub user_agent: String,
+    pub headers: Vec<(String, String)>,
+    pub cookie: Option<String>,
+    pub bearer_token: Option<String>,
+    pub max_redirects: usize,
+    pub timeout_secs: u64,
+}
+
+impl RequestContext {
+    pub fn new() -> Self {
+        Self::with_settings(
+            format!("ogp-checker/{}", env!("CARGO_PKG_VERSION")),
+            Vec::new(),
+            None,
+            None,
+            10,
+            10,
+        )
+    }
+
+    pub fn with_settings(
+        user_agent: String,
+        headers: Vec<(String, String)>,
+        cookie: Option<String>,
+        bearer_token: Option<String>,
+        max_redirects: usize,
+        timeout_secs: u64,
+    ) -> Self {
+        let client = build_client(
+            &user_agent,
+            &headers,
+            cookie.as_deref(),
+            max_redirects,
+            timeout_secs,
+        )
+        .unwrap_or_else(|_| Client::new());
+
+        Self {
+            client,
+            user_agent,
+            headers,
+            cookie,
+            bearer_token,
+            max_redirects,
+            timeout_secs,
+        }
+    }
+
+    fn request(&self, method: Method, url: &str) -> reqwest::RequestBuilder {
+        let builder = self.client.request(method, url);
+        match &self.bearer_token {
+            Some(token) => builder.bearer_auth(token),
+            None => builder,
         }
     }
 }
 
-pub async fn update_ogp(state: Arc<Mutex<AppState>>, client: Client) {
+impl Default for RequestContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn build_client(
+    user_agent: &str,
+    headers: &[(String, String)],
+    cookie: Option<&str>,
+    max_redirects: usize,
+    timeout_secs: u64,
+) -> reqwest::Result<Client> {
+    let mut header_map = HeaderMap::new();
+    for (name, value) in headers {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            header_map.insert(name, value);
+        }
+    }
+    if let Some(cookie) = cookie {
+        if let Ok(value) = HeaderValue::from_str(cookie) {
+            header_map.insert(COOKIE, value);
+        }
+    }
+
+    Client::builder()
+        .user_agent(user_agent)
+        .redirect(reqwest::redirect::Policy::limited(max_redirects))
+        .default_headers(header_map)
+        .timeout(std::time::Duration::from_secs(timeout_secs))
+        .build()
+}
+
+pub async fn update_ogp(state: Arc<Mutex<AppState>>, ctx: RequestContext) {
     let url;
+    let resize_filter;
+    let tab_id;
     {
         let state = state.lock().await;
-        url = state.normalize_url();
+        tab_id = state.active_tab().id;
+        url = state.active_tab().normalize_url();
+        resize_filter = state.resize_filter;
     }
 
-    let ogp_result = fetch_ogp_info(&client, &url).await;
+    let ogp_result = fetch_ogp_info(&ctx, &url).await;
     let dynamic_img_result = if let Ok(ref ogp_info) = ogp_result {
-        fetch_dynamic_image(&client, &ogp_info.image).await.ok()
+        fetch_dynamic_image(&ctx, &ogp_info.image).await.ok()
     } else {
         None
     };
@@ -70,207 +314,65 @@ pub async fn update_ogp(state: Arc<Mutex<AppState>>, client: Client) {
     let mut state = state.lock().await;
     match ogp_result {
         Ok(ogp_info) => {
-            state.ogp_info = Some(ogp_info);
-            state.cached_image = dynamic_img_result.map(Image::from_dynamic_image);
-            state.error_message = None;
+            history::record(&mut state.history, &url);
+            // The tab this fetch started from may have closed or moved while
+            // we were awaiting the network; look it up by its stable id
+            // rather than trusting a `tabs` position captured before the await.
+            if let Some(tab) = state.tab_by_id_mut(tab_id) {
+                tab.validation = validate::validate(&ogp_info);
+                tab.ogp_info = Some(ogp_info);
+                tab.cached_image = dynamic_img_result
+                    .map(|img| Image::from_dynamic_image_with_filter(&img, resize_filter));
+                tab.image_version = tab.image_version.wrapping_add(1);
+                tab.error_message = None;
+                tab.status_message = None;
+                tab.metadata_offset = 0;
+            }
         }
         Err(err) => {
-            state.error_message = Some(format!("Failed to fetch OGP info: {}", err));
+            if let Some(tab) = state.tab_by_id_mut(tab_id) {
+                tab.error_message = Some(format!("Failed to fetch OGP info: {}", err));
+                tab.status_message = None;
+            }
         }
     }
 }
 
-pub async fn display_ogp() {
-    enable_raw_mode().unwrap();
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen).unwrap();
-    let backend = CrosstermBackend::new(stdout);
-    let terminal = Arc::new(tokio::sync::Mutex::new(Terminal::new(backend).unwrap()));
-
-    let state = Arc::new(tokio::sync::Mutex::new(AppState::new()));
-    let client = Client::new();
-
-    let (tx, rx) = watch::channel(());
-    let rx = Arc::new(tokio::sync::Mutex::new(rx));
-    let mut needs_redraw = true;
-
-    let rx_clone = Arc::clone(&rx);
-    let state_clone = Arc::clone(&state);
-    let terminal_clone = Arc::clone(&terminal);
-
-    tokio::spawn(async move {
-        loop {
-            if needs_redraw || rx_clone.lock().await.changed().await.is_ok() {
-                needs_redraw = false;
-
-                let state = state_clone.lock().await;
-                let mut terminal = terminal_clone.lock().await;
-
-                if let Err(e) = terminal.draw(|f| {
-                    let size = f.area();
-                    let chunks = Layout::default()
-                        .direction(Direction::Vertical)
-                        .constraints([
-                            Constraint::Length(3),
-                            Constraint::Length(3),
-                            Constraint::Percentage(94),
-                        ])
-                        .split(size);
-
-                    let mut url_display = state.url.clone();
-                    if state.cursor_position <= state.url.len() {
-                        url_display.insert(state.cursor_position, '|');
-                    }
-
-                    let url_input = Paragraph::new(url_display)
-                        .block(Block::default().borders(Borders::ALL).title("Enter URL"))
-                        .style(Style::default());
-                    f.render_widget(url_input, chunks[0]);
-
-                    if let Some(error_message) = &state.error_message {
-                        let error_paragraph = Paragraph::new(error_message.clone())
-                            .block(Block::default().borders(Borders::ALL).title("Error"))
-                            .style(Style::default().fg(Color::Red));
-                        f.render_widget(error_paragraph, chunks[1]);
-                    }
-
-                    let content_chunks = Layout::default()
-                        .direction(Direction::Horizontal)
-                        .constraints([
-                            Constraint::Percentage(33),
-                            Constraint::Percentage(67),
-                        ])
-                        .split(chunks[2]);
-
-                    if let Some(info) = &state.ogp_info {
-                        let meta_info = format!(
-                            "Title: {}\nDescription: {}\nImage: {}\nMetadata: {} items",
-                            info.title, info.description, info.image, info.metadata.len()
-                        );
-
-                        let meta_paragraph = Paragraph::new(meta_info)
-                            .block(Block::default().borders(Borders::ALL).title("OGP Info"));
-                        f.render_widget(meta_paragraph, content_chunks[1]);
-
-                        if let Some(cached_image) = &state.cached_image {
-                            draw_image_with_colors(f, content_chunks[0], cached_image);
-                        } else {
-                            let empty_paragraph = Paragraph::new("No image available")
-                                .block(Block::default().borders(Borders::ALL).title("Image"));
-                            f.render_widget(empty_paragraph, content_chunks[0]);
-                        }
-                    }
-                }) {
-                    eprintln!("Error drawing terminal: {}", e);
-                }
-            }
-        }
-    });
-
-    loop {
-        if let Event::Key(key) = event::read().unwrap() {
-            let mut should_update = false;
-
-            match key.code {
-                KeyCode::Char(c) => {
-                    let mut state = state.lock().await;
-                    let cursor_position = state.cursor_position;
-                    state.url.insert(cursor_position, c);
-                    state.cursor_position += 1;
-                    should_update = true;
-                }
-                KeyCode::Backspace => {
-                    let mut state = state.lock().await;
-                    let cursor_position = state.cursor_position;
-                    if cursor_position > 0 {
-                        state.url.remove(cursor_position - 1);
-                        state.cursor_position -= 1;
-                        should_update = true;
-                    }
-                }
-                KeyCode::Left => {
-                    let mut state = state.lock().await;
-                    if state.cursor_position > 0 {
-                        state.cursor_position -= 1;
-                        should_update = true;
-                    }
-                }
-                KeyCode::Right => {
-                    let mut state = state.lock().await;
-                    if state.cursor_position < state.url.len() {
-                        state.cursor_position += 1;
-                        should_update = true;
-                    }
-                }
-                KeyCode::Enter => {
-                    let url_is_empty;
-                    {
-                        let mut state = state.lock().await;
-                        url_is_empty = state.url.is_empty();
-
-                        if url_is_empty {
-                            state.ogp_info = None;
-                            state.cached_image = None;
-                            state.error_message = None;
-                        }
-                    }
-                    if !url_is_empty {
-                        let state_clone = Arc::clone(&state);
-                        let client_clone = client.clone();
-                        let tx_clone = tx.clone();
-
-                        tokio::spawn(async move {
-                            update_ogp(state_clone, client_clone).await;
-                            let _ = tx_clone.send(());
-                        });
-                    } else {
-                        let _ = tx.send(());
-                    }
-                }
-                KeyCode::Esc => break,
-                _ => {}
-            }
+/// Meta tag key prefixes recognized as Open Graph / Twitter Card data.
+/// `og:type`, `og:url`, `og:site_name`, `og:locale`, `og:image:width/height/alt`
+/// all fall under the `og:` prefix; `article:*` and `product:*` are the
+/// type-specific OGP extensions.
+const RECOGNIZED_PREFIXES: [&str; 4] = ["og:", "twitter:", "article:", "product:"];
 
-            if should_update {
-                let _ = tx.send(());
-            }
+pub async fn fetch_ogp_info(ctx: &RequestContext, url: &str) -> Result<OGPInfo, reqwest::Error> {
+    let res = ctx.request(Method::GET, url).send().await?.text().await?;
+    let document = Html::parse_document(&res);
+    let meta_selector = Selector::parse("meta").unwrap();
+
+    let mut tags = BTreeMap::new();
+    for element in document.select(&meta_selector) {
+        let value = element.value();
+        let Some(content) = value.attr("content") else {
+            continue;
+        };
+        let Some(key) = value.attr("property").or_else(|| value.attr("name")) else {
+            continue;
+        };
+
+        if RECOGNIZED_PREFIXES.iter().any(|prefix| key.starts_with(prefix)) {
+            tags.insert(key.to_string(), content.to_string());
         }
     }
 
-    disable_raw_mode().unwrap();
-    let mut terminal = terminal.lock().await;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen).unwrap();
-    terminal.show_cursor().unwrap();
-}
+    let title = tags.get("og:title").cloned().unwrap_or_default();
+    let description = tags.get("og:description").cloned().unwrap_or_default();
+    let image = tags.get("og:image").cloned().unwrap_or_default();
 
-async fn fetch_ogp_info(client: &Client, url: &str) -> Result<OGPInfo, reqwest::Error> {
-    let res = client.get(url).send().await?.text().await?;
-    let document = Html::parse_document(&res);
-    let title = document.select(&Selector::parse("meta[property='og:title']").unwrap())
-        .next()
-        .and_then(|e| e.value().attr("content"))
-        .unwrap_or("")
-        .to_string();
-    let description = document.select(&Selector::parse("meta[property='og:description']").unwrap())
-        .next()
-        .and_then(|e| e.value().attr("content"))
-        .unwrap_or("")
-        .to_string();
-    let image = document.select(&Selector::parse("meta[property='og:image']").unwrap())
-        .next()
-        .and_then(|e| e.value().attr("content"))
-        .unwrap_or("")
-        .to_string();
-    let metadata = document.select(&Selector::parse("meta").unwrap())
-        .filter_map(|e| e.value().attr("content"))
-        .map(|s| s.to_string())
-        .collect();
-
-    Ok(OGPInfo { title, description, image, metadata })
+    Ok(OGPInfo { title, description, image, tags })
 }
 
-async fn fetch_dynamic_image(client: &Client, url: &str) -> Result<DynamicImage, io::Error> {
-    let res = client.get(url).send().await.map_err(|err| {
+async fn fetch_dynamic_image(ctx: &RequestContext, url: &str) -> Result<DynamicImage, io::Error> {
+    let res = ctx.request(Method::GET, url).send().await.map_err(|err| {
         io::Error::new(io::ErrorKind::Other, format!("HTTP request failed: {}", err))
     })?;
     let bytes = res.bytes().await.map_err(|err| {
@@ -282,37 +384,3 @@ async fn fetch_dynamic_image(client: &Client, url: &str) -> Result<DynamicImage,
     })
 }
 
-fn draw_image_with_colors(
-    f: &mut ratatui::Frame,
-    area: ratatui::layout::Rect,
-    img: &Image,
-) {
-    let (target_width, target_height) = (area.width as usize, area.height as usize);
-
-    let canvas = Canvas::default()
-        .block(Block::default().borders(Borders::ALL).title("Image"))
-        .paint(|ctx| {
-            for y in 0..target_height {
-                for x in 0..target_width {
-                    let src_x = x * (img.width as usize) / target_width;
-                    let src_y = y * (img.height as usize) / target_height;
-                    let idx = src_y * (img.width as usize) + src_x;
-
-                    let (r, g, b) = img.pixels[idx];
-                    let color = Color::Rgb(r, g, b);
-
-                    ctx.draw(&Rectangle {
-                        x: x as f64,
-                        y: (target_height - 1 - y) as f64,
-                        width: 1.0,
-                        height: 1.0,
-                        color,
-                    });
-                }
-            }
-        })
-        .x_bounds([0.0, target_width as f64])
-        .y_bounds([0.0, target_height as f64]);
-
-    f.render_widget(canvas, area);
-}
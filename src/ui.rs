@@ -1,34 +1,54 @@
 use crate::{
-    image::Image,
-    ogp::{update_ogp, AppState},
+    config,
+    image::{encode_kitty, encode_sixel, Image, RenderBackend},
+    ogp::{update_ogp, AppState, RequestContext},
 };
 use crossterm::{
-    event::{self, Event, KeyCode},
+    cursor::MoveTo,
+    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Style},
     widgets::{
         canvas::{Canvas, Rectangle},
-        Block, Borders, Paragraph,
+        Block, Borders, Paragraph, Tabs,
     },
     Terminal,
 };
+use std::io::Write;
 use std::{io, sync::Arc};
 use tokio::sync::{watch, Mutex};
 
 pub struct UI {
     state: Arc<Mutex<AppState>>,
+    /// Rebuilt in place by `F(5)` so a live config reload can pick up a new
+    /// `user_agent`/`request_timeout_secs` without restarting; everything
+    /// else on `UI` only ever reads it, so a `Mutex` is all the sharing needs.
+    request_context: Mutex<RequestContext>,
+    config_path: std::path::PathBuf,
     tx: watch::Sender<()>,
 }
 
 impl UI {
     pub fn new(state: Arc<Mutex<AppState>>) -> Self {
+        Self::with_request_context(state, RequestContext::new())
+    }
+
+    pub fn with_request_context(state: Arc<Mutex<AppState>>, request_context: RequestContext) -> Self {
+        Self::with_config_path(state, request_context, config::default_path())
+    }
+
+    pub fn with_config_path(
+        state: Arc<Mutex<AppState>>,
+        request_context: RequestContext,
+        config_path: std::path::PathBuf,
+    ) -> Self {
         let (tx, _) = watch::channel(());
-        UI { state, tx }
+        UI { state, request_context: Mutex::new(request_context), config_path, tx }
     }
 
     pub async fn run(&self) -> Result<(), io::Error> {
@@ -46,19 +66,36 @@ impl UI {
 
         tokio::spawn(async move {
             let mut rx = rx;
+            // (tab id, image_version) of the placement currently on screen, if
+            // any, so we only retransmit when the picture actually changed and
+            // know to clear it when it goes away or the active tab switches.
+            let mut last_shown: Option<(u64, u64)> = None;
             loop {
                 rx.changed().await.unwrap();
                 let state = state_clone.lock().await;
                 let mut terminal = terminal_clone.lock().await;
+                let mut image_area = None;
                 terminal
-                    .draw(|f| UI::draw_ui(f, &state))
+                    .draw(|f| UI::draw_ui(f, &state, &mut image_area))
                     .expect("Failed to draw UI");
+
+                let tab = state.active_tab();
+                let current_image = tab.cached_image.as_ref().map(|_| (tab.id, tab.image_version));
+                if current_image != last_shown {
+                    if last_shown.is_some() {
+                        let _ = UI::clear_protocol_image(state.render_backend);
+                    }
+                    if let (Some(area), Some(cached_image)) = (image_area, &tab.cached_image) {
+                        let _ = UI::write_protocol_image(area, cached_image, state.render_backend);
+                    }
+                    last_shown = current_image;
+                }
             }
         });
 
         loop {
             if let Event::Key(key_event) = event::read()? {
-                if self.handle_input(key_event.code).await {
+                if self.handle_input(key_event).await {
                     break;
                 }
             }
@@ -71,16 +108,86 @@ impl UI {
         Ok(())
     }
 
-    fn draw_ui(f: &mut ratatui::Frame, state: &AppState) {
+    fn draw_ui(f: &mut ratatui::Frame, state: &AppState, image_area: &mut Option<Rect>) {
         let size = f.area();
+        let outer_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0)])
+            .split(size);
+
+        let tab_titles: Vec<String> = state
+            .tabs
+            .iter()
+            .enumerate()
+            .map(|(i, tab)| format!("{}: {}", i + 1, tab.label()))
+            .collect();
+        let tab_bar = Tabs::new(tab_titles)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Tabs (Tab/Shift+Tab to switch, Ctrl+T new, Ctrl+W close, Ctrl+H history)")
+                    .border_style(Style::default().fg(parse_color(&state.theme.border_color))),
+            )
+            .select(state.active_tab)
+            .highlight_style(Style::default().fg(Color::Black).bg(Color::White));
+        f.render_widget(tab_bar, outer_chunks[0]);
+
+        let tab = state.active_tab();
+
         let vertical_chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(3),
+                Constraint::Percentage(35),
+                Constraint::Percentage(25),
                 Constraint::Percentage(40),
-                Constraint::Percentage(60),
             ])
-            .split(size);
+            .split(outer_chunks[1]);
+
+        let mut url_display = tab.url.clone();
+        if tab.cursor_position <= tab.url.len() {
+            url_display.insert(tab.cursor_position, '|');
+        }
+
+        let url_input = Paragraph::new(url_display)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Enter URL")
+                    .border_style(Style::default().fg(parse_color(&state.theme.border_color))),
+            )
+            .style(Style::default());
+        f.render_widget(url_input, vertical_chunks[0]);
+
+        if state.history_visible {
+            let history_lines: Vec<ratatui::text::Line> = if state.history.is_empty() {
+                vec![ratatui::text::Line::raw("No history yet")]
+            } else {
+                state
+                    .history
+                    .iter()
+                    .enumerate()
+                    .map(|(i, url)| {
+                        let style = if i == state.history_selected {
+                            Style::default().fg(Color::Black).bg(Color::White)
+                        } else {
+                            Style::default()
+                        };
+                        ratatui::text::Line::styled(url.clone(), style)
+                    })
+                    .collect()
+            };
+
+            let history_paragraph = Paragraph::new(history_lines).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("History (Up/Down to select, Enter to open, Esc to close)"),
+            );
+            f.render_widget(history_paragraph, vertical_chunks[1]);
+            f.render_widget(Block::default().borders(Borders::ALL), vertical_chunks[2]);
+            f.render_widget(Block::default().borders(Borders::ALL), vertical_chunks[3]);
+            return;
+        }
 
         let image_and_info_chunks = Layout::default()
             .direction(Direction::Horizontal)
@@ -90,37 +197,37 @@ impl UI {
             ])
             .split(vertical_chunks[1]);
 
-        let mut url_display = state.url.clone();
-        if state.cursor_position <= state.url.len() {
-            url_display.insert(state.cursor_position, '|');
-        }
-
-        let url_input = Paragraph::new(url_display)
-            .block(Block::default().borders(Borders::ALL).title("Enter URL"))
-            .style(Style::default());
-        f.render_widget(url_input, vertical_chunks[0]);
-
-        if let Some(error_message) = &state.error_message {
+        if let Some(error_message) = &tab.error_message {
             let error_paragraph = Paragraph::new(error_message.clone())
                 .block(Block::default().borders(Borders::ALL).title("Error"))
-                .style(Style::default().fg(Color::Red));
+                .style(Style::default().fg(parse_color(&state.theme.error_color)));
             f.render_widget(error_paragraph, image_and_info_chunks[1]);
-        } else if let Some(info) = &state.ogp_info {
+        } else if let Some(info) = &tab.ogp_info {
             let ogp_info_display = format!(
-                "Title: {}\nDescription: {}\nImage URL: {}\nMetadata Count: {}",
+                "Title: {}\nDescription: {}\nImage URL: {}\nTag Count: {}",
                 info.title,
                 info.description,
                 info.image,
-                info.metadata.len()
+                info.tags.len()
             );
 
+            let title = match &tab.status_message {
+                Some(status) => format!("OGP Info — {}", status),
+                None => "OGP Info".to_string(),
+            };
             let ogp_info_paragraph = Paragraph::new(ogp_info_display)
-                .block(Block::default().borders(Borders::ALL).title("OGP Info"))
+                .block(Block::default().borders(Borders::ALL).title(title))
                 .style(Style::default());
             f.render_widget(ogp_info_paragraph, image_and_info_chunks[1]);
 
-            if let Some(cached_image) = &state.cached_image {
-                UI::draw_image_with_colors(f, image_and_info_chunks[0], cached_image);
+            if let Some(cached_image) = &tab.cached_image {
+                if state.render_backend == RenderBackend::Canvas {
+                    UI::draw_image_with_colors(f, image_and_info_chunks[0], cached_image);
+                } else {
+                    let placeholder = Block::default().borders(Borders::ALL).title("Image");
+                    f.render_widget(placeholder, image_and_info_chunks[0]);
+                    *image_area = Some(image_and_info_chunks[0]);
+                }
             } else {
                 let empty_paragraph = Paragraph::new("No image available")
                     .block(Block::default().borders(Borders::ALL).title("Image"));
@@ -128,12 +235,42 @@ impl UI {
             }
         }
 
-        if let Some(info) = &state.ogp_info {
+        if !tab.validation.is_empty() {
+            let validation_lines: Vec<ratatui::text::Line> = tab
+                .validation
+                .iter()
+                .map(|issue| {
+                    let color = match issue.severity {
+                        crate::validate::Severity::Error => {
+                            parse_color(&state.theme.validation_error_color)
+                        }
+                        crate::validate::Severity::Warning => {
+                            parse_color(&state.theme.validation_warning_color)
+                        }
+                    };
+                    ratatui::text::Line::styled(
+                        format!("[{:?}] {}", issue.severity, issue.message),
+                        Style::default().fg(color),
+                    )
+                })
+                .collect();
+
+            let validation_paragraph = Paragraph::new(validation_lines)
+                .block(Block::default().borders(Borders::ALL).title("Validation"));
+            f.render_widget(validation_paragraph, vertical_chunks[2]);
+        } else if tab.ogp_info.is_some() {
+            let ok_paragraph = Paragraph::new("No issues found")
+                .block(Block::default().borders(Borders::ALL).title("Validation"))
+                .style(Style::default().fg(Color::Green));
+            f.render_widget(ok_paragraph, vertical_chunks[2]);
+        }
+
+        if let Some(info) = &tab.ogp_info {
             let metadata_to_display = info
-                .metadata
+                .tags
                 .iter()
-                .skip(state.metadata_offset)
-                .take((vertical_chunks[2].height - 2) as usize)
+                .skip(tab.metadata_offset)
+                .take((vertical_chunks[3].height - 2) as usize)
                 .map(|(tag, content)| format!("{}: {}", tag, content))
                 .collect::<Vec<_>>()
                 .join("\n");
@@ -141,8 +278,44 @@ impl UI {
             let metadata_paragraph = Paragraph::new(metadata_to_display)
                 .block(Block::default().borders(Borders::ALL).title("Metadata"))
                 .style(Style::default());
-            f.render_widget(metadata_paragraph, vertical_chunks[2]);
+            f.render_widget(metadata_paragraph, vertical_chunks[3]);
+        }
+    }
+
+    /// Emit the image directly to stdout using the Kitty or Sixel protocol,
+    /// positioning the cursor at the first cell inside `area`'s border. Does nothing for
+    /// `RenderBackend::Canvas`, which is drawn through the ratatui widget tree instead.
+    fn write_protocol_image(area: Rect, img: &Image, backend: RenderBackend) -> io::Result<()> {
+        let mut stdout = io::stdout();
+        match backend {
+            RenderBackend::Canvas => Ok(()),
+            RenderBackend::Kitty => {
+                execute!(stdout, MoveTo(area.x + 1, area.y + 1))?;
+                for chunk in encode_kitty(img) {
+                    stdout.write_all(chunk.as_bytes())?;
+                }
+                stdout.flush()
+            }
+            RenderBackend::Sixel => {
+                execute!(stdout, MoveTo(area.x + 1, area.y + 1))?;
+                stdout.write_all(encode_sixel(img).as_bytes())?;
+                stdout.flush()
+            }
+        }
+    }
+
+    /// Undisplay a previously-written protocol image. Kitty has an explicit
+    /// delete op for this; Sixel has no equivalent (a sixel image is just
+    /// pixels painted into the scrollback, not a deletable placement), so the
+    /// best we can do there is stop retransmitting it, which `last_shown`
+    /// already handles.
+    fn clear_protocol_image(backend: RenderBackend) -> io::Result<()> {
+        if backend == RenderBackend::Kitty {
+            let mut stdout = io::stdout();
+            stdout.write_all(b"\x1b_Ga=d\x1b\\")?;
+            stdout.flush()?;
         }
+        Ok(())
     }
 
     fn draw_image_with_colors(f: &mut ratatui::Frame, area: ratatui::layout::Rect, img: &Image) {
@@ -176,68 +349,200 @@ impl UI {
         f.render_widget(canvas, area);
     }
 
-    async fn handle_input(&self, key: KeyCode) -> bool {
+    async fn handle_input(&self, key_event: KeyEvent) -> bool {
         let mut state = self.state.lock().await;
-        match key {
-            KeyCode::Char(c) => {
-                let cursor_position = state.cursor_position;
-                state.url.insert(cursor_position, c);
-                state.cursor_position += 1;
+        match key_event.code {
+            KeyCode::Char('t') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                state.open_tab();
+                self.tx.send(()).unwrap();
+            }
+            KeyCode::Char('w') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                state.close_active_tab();
+                self.tx.send(()).unwrap();
+            }
+            KeyCode::Char('h') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                state.history_visible = !state.history_visible;
+                state.history_selected = 0;
+                self.tx.send(()).unwrap();
+            }
+            KeyCode::Tab => {
+                state.next_tab();
+                self.tx.send(()).unwrap();
+            }
+            KeyCode::BackTab => {
+                state.prev_tab();
+                self.tx.send(()).unwrap();
+            }
+            KeyCode::Char(c)
+                if key_event.modifiers.is_empty() || key_event.modifiers == KeyModifiers::SHIFT =>
+            {
+                let tab = state.active_tab_mut();
+                let cursor_position = tab.cursor_position;
+                tab.url.insert(cursor_position, c);
+                tab.cursor_position += 1;
                 self.tx.send(()).unwrap();
             }
             KeyCode::Backspace => {
-                let cursor_position = state.cursor_position;
+                let tab = state.active_tab_mut();
+                let cursor_position = tab.cursor_position;
                 if cursor_position > 0 {
-                    state.url.remove(cursor_position - 1);
-                    state.cursor_position -= 1;
+                    tab.url.remove(cursor_position - 1);
+                    tab.cursor_position -= 1;
                     self.tx.send(()).unwrap();
                 }
             }
             KeyCode::Left => {
-                if state.cursor_position > 0 {
-                    state.cursor_position -= 1;
+                let tab = state.active_tab_mut();
+                if tab.cursor_position > 0 {
+                    tab.cursor_position -= 1;
                     self.tx.send(()).unwrap();
                 }
             }
             KeyCode::Right => {
-                if state.cursor_position < state.url.len() {
-                    state.cursor_position += 1;
+                let tab = state.active_tab_mut();
+                if tab.cursor_position < tab.url.len() {
+                    tab.cursor_position += 1;
+                    self.tx.send(()).unwrap();
+                }
+            }
+            KeyCode::Up if state.history_visible => {
+                if state.history_selected > 0 {
+                    state.history_selected -= 1;
+                    self.tx.send(()).unwrap();
+                }
+            }
+            KeyCode::Down if state.history_visible => {
+                if state.history_selected + 1 < state.history.len() {
+                    state.history_selected += 1;
                     self.tx.send(()).unwrap();
                 }
             }
             KeyCode::Up => {
-                if state.metadata_offset > 0 {
-                    state.metadata_offset -= 1;
+                let tab = state.active_tab_mut();
+                if tab.metadata_offset > 0 {
+                    tab.metadata_offset -= 1;
                     self.tx.send(()).unwrap();
                 }
             }
             KeyCode::Down => {
-                if let Some(info) = &state.ogp_info {
-                    if state.metadata_offset + 1 < info.metadata.len() {
-                        state.metadata_offset += 1;
+                let tab = state.active_tab_mut();
+                if let Some(info) = &tab.ogp_info {
+                    if tab.metadata_offset + 1 < info.tags.len() {
+                        tab.metadata_offset += 1;
                         self.tx.send(()).unwrap();
                     }
                 }
             }
+            KeyCode::Enter if state.history_visible => {
+                if state.history.is_empty() {
+                    return false;
+                }
+                state.open_history_entry(state.history_selected);
+                let state_clone = Arc::clone(&self.state);
+                let ctx_clone = self.request_context.lock().await.clone();
+                let tx_clone = self.tx.clone();
+                tokio::spawn(async move {
+                    update_ogp(state_clone, ctx_clone).await;
+                    tx_clone.send(()).unwrap();
+                });
+            }
             KeyCode::Enter => {
-                if state.url.is_empty() {
-                    state.ogp_info = None;
-                    state.cached_image = None;
-                    state.error_message = None;
+                let tab = state.active_tab_mut();
+                if tab.url.is_empty() {
+                    tab.ogp_info = None;
+                    tab.cached_image = None;
+                    tab.image_version = tab.image_version.wrapping_add(1);
+                    tab.error_message = None;
+                    tab.status_message = None;
                     self.tx.send(()).unwrap();
                 } else {
                     let state_clone = Arc::clone(&self.state);
+                    let ctx_clone = self.request_context.lock().await.clone();
                     let tx_clone = self.tx.clone();
                     tokio::spawn(async move {
-                        let client = reqwest::Client::new();
-                        update_ogp(state_clone, client).await;
+                        update_ogp(state_clone, ctx_clone).await;
                         tx_clone.send(()).unwrap();
                     });
                 }
             }
+            KeyCode::F(5) => {
+                let config = config::load_from(&self.config_path);
+                if let Some(name) = &config.render_backend {
+                    if let Some(backend) = RenderBackend::from_name(name) {
+                        state.render_backend = backend;
+                    }
+                }
+                if let Some(filter) = crate::image::ResizeFilter::from_name(&config.resize_filter) {
+                    state.resize_filter = filter;
+                }
+                state.theme = config.theme;
+
+                let mut ctx = self.request_context.lock().await;
+                let user_agent = config
+                    .user_agent
+                    .clone()
+                    .unwrap_or_else(|| ctx.user_agent.clone());
+                *ctx = RequestContext::with_settings(
+                    user_agent,
+                    ctx.headers.clone(),
+                    ctx.cookie.clone(),
+                    ctx.bearer_token.clone(),
+                    ctx.max_redirects,
+                    config.request_timeout_secs,
+                );
+                drop(ctx);
+
+                self.tx.send(()).unwrap();
+            }
+            KeyCode::F(6) => {
+                let path = std::env::current_dir()
+                    .unwrap_or_default()
+                    .join("ogp-card.html");
+                let html_result = state
+                    .active_tab()
+                    .ogp_info
+                    .as_ref()
+                    .map(|info| crate::html_export::write_to(info, &path));
+                match html_result {
+                    Some(Err(e)) => {
+                        let tab = state.active_tab_mut();
+                        tab.error_message = Some(format!("Failed to export HTML preview: {}", e));
+                        tab.status_message = None;
+                        self.tx.send(()).unwrap();
+                    }
+                    Some(Ok(())) => {
+                        let tab = state.active_tab_mut();
+                        tab.error_message = None;
+                        tab.status_message = Some(format!("Wrote HTML preview to {}", path.display()));
+                        self.tx.send(()).unwrap();
+                    }
+                    None => {}
+                }
+            }
+            KeyCode::Esc if state.history_visible => {
+                state.history_visible = false;
+                self.tx.send(()).unwrap();
+            }
             KeyCode::Esc => return true,
             _ => {}
         }
         false
     }
 }
+
+/// Parse a config color name into a `ratatui::style::Color`, falling back to
+/// white for anything unrecognized so a typo in `config.toml` never breaks rendering.
+fn parse_color(name: &str) -> Color {
+    match name.to_ascii_lowercase().as_str() {
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "black" => Color::Black,
+        "gray" | "grey" => Color::Gray,
+        "white" => Color::White,
+        _ => Color::White,
+    }
+}
@@ -0,0 +1,180 @@
+use crate::ogp::OGPInfo;
+
+/// Recommended length range for `og:description`, per the OGP authoring
+/// guidance most crawlers (Facebook, Discord, Slack) truncate around.
+const DESCRIPTION_MIN_LEN: usize = 60;
+const DESCRIPTION_MAX_LEN: usize = 160;
+
+const REQUIRED_TAGS: [&str; 4] = ["og:title", "og:type", "og:image", "og:url"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ValidationIssue {
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// Check `info` against the OGP/Twitter Card spec: required tags present,
+/// `og:image` is an absolute URL, `og:description` length within the
+/// recommended range, and `twitter:card` has a matching image. Errors are
+/// ordered before warnings.
+pub fn validate(info: &OGPInfo) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    for required in REQUIRED_TAGS {
+        if !info.tags.contains_key(required) {
+            issues.push(ValidationIssue {
+                severity: Severity::Error,
+                message: format!("missing required tag \"{}\"", required),
+            });
+        }
+    }
+
+    if let Some(image) = info.tags.get("og:image") {
+        if !(image.starts_with("http://") || image.starts_with("https://")) {
+            issues.push(ValidationIssue {
+                severity: Severity::Error,
+                message: "og:image should be an absolute URL".to_string(),
+            });
+        }
+    }
+
+    if !info.description.is_empty() {
+        let len = info.description.chars().count();
+        if len < DESCRIPTION_MIN_LEN || len > DESCRIPTION_MAX_LEN {
+            issues.push(ValidationIssue {
+                severity: Severity::Warning,
+                message: format!(
+                    "og:description is {} characters; the recommended range is {}-{}",
+                    len, DESCRIPTION_MIN_LEN, DESCRIPTION_MAX_LEN
+                ),
+            });
+        }
+    }
+
+    if info.tags.contains_key("twitter:card")
+        && !info.tags.keys().any(|key| key.starts_with("twitter:image"))
+        && !info.tags.contains_key("og:image")
+    {
+        issues.push(ValidationIssue {
+            severity: Severity::Warning,
+            message: "twitter:card is present without a matching image".to_string(),
+        });
+    }
+
+    issues.sort_by_key(|issue| issue.severity != Severity::Error);
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn info_with_tags(tags: &[(&str, &str)]) -> OGPInfo {
+        let tags: BTreeMap<String, String> = tags
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        OGPInfo {
+            title: tags.get("og:title").cloned().unwrap_or_default(),
+            description: tags.get("og:description").cloned().unwrap_or_default(),
+            image: tags.get("og:image").cloned().unwrap_or_default(),
+            tags,
+        }
+    }
+
+    #[test]
+    fn flags_every_missing_required_tag() {
+        let info = info_with_tags(&[]);
+        let issues = validate(&info);
+        assert_eq!(issues.len(), REQUIRED_TAGS.len());
+        assert!(issues.iter().all(|issue| issue.severity == Severity::Error));
+    }
+
+    #[test]
+    fn complete_valid_tags_produce_no_issues() {
+        let info = info_with_tags(&[
+            ("og:title", "A Title"),
+            ("og:type", "website"),
+            ("og:image", "https://example.com/image.png"),
+            ("og:url", "https://example.com"),
+            (
+                "og:description",
+                "A description that comfortably sits within the recommended length range.",
+            ),
+        ]);
+        assert!(validate(&info).is_empty());
+    }
+
+    #[test]
+    fn relative_image_url_is_an_error() {
+        let info = info_with_tags(&[
+            ("og:title", "A Title"),
+            ("og:type", "website"),
+            ("og:image", "/image.png"),
+            ("og:url", "https://example.com"),
+        ]);
+        let issues = validate(&info);
+        assert!(issues
+            .iter()
+            .any(|issue| issue.severity == Severity::Error
+                && issue.message.contains("absolute URL")));
+    }
+
+    #[test]
+    fn description_outside_recommended_range_warns() {
+        let info = info_with_tags(&[
+            ("og:title", "A Title"),
+            ("og:type", "website"),
+            ("og:image", "https://example.com/image.png"),
+            ("og:url", "https://example.com"),
+            ("og:description", "Too short"),
+        ]);
+        let issues = validate(&info);
+        assert!(issues
+            .iter()
+            .any(|issue| issue.severity == Severity::Warning
+                && issue.message.contains("recommended range")));
+    }
+
+    #[test]
+    fn twitter_card_without_any_image_warns() {
+        let info = info_with_tags(&[("twitter:card", "summary")]);
+        let issues = validate(&info);
+        assert!(issues
+            .iter()
+            .any(|issue| issue.severity == Severity::Warning
+                && issue.message.contains("twitter:card")));
+    }
+
+    #[test]
+    fn twitter_card_with_og_image_does_not_warn() {
+        let info = info_with_tags(&[
+            ("og:title", "A Title"),
+            ("og:type", "website"),
+            ("og:image", "https://example.com/image.png"),
+            ("og:url", "https://example.com"),
+            ("twitter:card", "summary"),
+        ]);
+        assert!(validate(&info).is_empty());
+    }
+
+    #[test]
+    fn errors_sort_before_warnings() {
+        let info = info_with_tags(&[("og:description", "Too short")]);
+        let issues = validate(&info);
+        let first_warning = issues
+            .iter()
+            .position(|issue| issue.severity == Severity::Warning)
+            .unwrap();
+        assert!(issues[..first_warning]
+            .iter()
+            .all(|issue| issue.severity == Severity::Error));
+    }
+}